@@ -64,6 +64,23 @@ pub enum JoinType {
     ///
     /// [1]: http://btw2017.informatik.uni-stuttgart.de/slidesandpapers/F1-10-37/paper_web.pdf
     LeftMark,
+    /// Group join variants.
+    ///
+    /// A group join fuses a `GROUP BY` directly into the join that feeds it: instead of
+    /// joining and then aggregating, it aggregates the non-preserved side's rows into
+    /// each preserved-side group as the join probes. `LeftGroup`/`RightGroup` preserve
+    /// one side (an unmatched preserved row still produces a group, with `count`
+    /// aggregating to 0 and other aggregates to NULL); `InnerGroup` preserves neither
+    /// side's unmatched rows; `FullGroup` preserves both. See
+    /// "Accelerating Queries with Group-By and Join by Groupjoin" (Moerkotte & Neumann),
+    /// <https://www.vldb.org/pvldb/vol4/p843-moerkotte.pdf>.
+    InnerGroup,
+    /// Left Group Join - see [`JoinType::InnerGroup`].
+    LeftGroup,
+    /// Right Group Join - see [`JoinType::InnerGroup`].
+    RightGroup,
+    /// Full Group Join - see [`JoinType::InnerGroup`].
+    FullGroup,
 }
 
 impl JoinType {
@@ -74,6 +91,15 @@ impl JoinType {
         }
     }
 
+    /// Returns `true` if this is one of the group-join variants (see
+    /// [`JoinType::InnerGroup`]).
+    pub fn is_group_join(self) -> bool {
+        matches!(
+            self,
+            JoinType::InnerGroup | JoinType::LeftGroup | JoinType::RightGroup | JoinType::FullGroup
+        )
+    }
+
     /// Returns the `JoinType` if the (2) inputs were swapped
     ///
     /// Panics if [`Self::supports_swap`] returns false
@@ -84,6 +110,10 @@ impl JoinType {
             JoinType::Outer(side) => JoinType::Outer(side.negate()),
             JoinType::Semi(side) => JoinType::Semi(side.negate()),
             JoinType::Anti(side) => JoinType::Anti(side.negate()),
+            JoinType::InnerGroup => JoinType::InnerGroup,
+            JoinType::FullGroup => JoinType::FullGroup,
+            JoinType::LeftGroup => JoinType::RightGroup,
+            JoinType::RightGroup => JoinType::LeftGroup,
             JoinType::LeftMark => {
                 unreachable!("LeftMark join type does not support swapping")
             }
@@ -99,6 +129,10 @@ impl JoinType {
                 | JoinType::Outer(_)
                 | JoinType::Semi(_)
                 | JoinType::Anti(_)
+                | JoinType::InnerGroup
+                | JoinType::LeftGroup
+                | JoinType::RightGroup
+                | JoinType::FullGroup
         )
     }
 }
@@ -115,6 +149,10 @@ impl Display for JoinType {
             JoinType::Anti(JoinSide::Left) => "LeftAnti",
             JoinType::Anti(JoinSide::Right) => "RightAnti",
             JoinType::LeftMark => "LeftMark",
+            JoinType::InnerGroup => "InnerGroup",
+            JoinType::LeftGroup => "LeftGroup",
+            JoinType::RightGroup => "RightGroup",
+            JoinType::FullGroup => "FullGroup",
         };
         write!(f, "{join_type}")
     }
@@ -135,6 +173,10 @@ impl FromStr for JoinType {
             "LEFTANTI" => Ok(JoinType::Anti(JoinSide::Left)),
             "RIGHTANTI" => Ok(JoinType::Anti(JoinSide::Right)),
             "LEFTMARK" => Ok(JoinType::LeftMark),
+            "INNERGROUP" => Ok(JoinType::InnerGroup),
+            "LEFTGROUP" => Ok(JoinType::LeftGroup),
+            "RIGHTGROUP" => Ok(JoinType::RightGroup),
+            "FULLGROUP" => Ok(JoinType::FullGroup),
             _ => _not_impl_err!("The join type {s} does not exist or is not implemented"),
         }
     }
@@ -0,0 +1,314 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`EliminateOuterJoin`] converts outer joins to inner joins when a filter rejects nulls
+
+use crate::{OptimizerConfig, OptimizerRule};
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::{Column, DFSchemaRef, Result};
+use datafusion_expr::logical_plan::builder::build_join_schema;
+use datafusion_expr::{BinaryExpr, Expr, Filter, JoinSide, JoinType, LogicalPlan, Operator};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Eliminates outer joins when a `Filter` above them rejects the NULL-extended rows
+/// they would otherwise produce.
+///
+/// When a predicate like `b.x = 100`, `b.x > 0`, or `b.x IS NOT NULL` sits above a join
+/// and references a column from the join's null-supplying side, every row the outer
+/// join NULL-extends on that side is guaranteed to be filtered out by the predicate
+/// anyway, so the `Outer(JoinSide)`/`Full` join can be narrowed to `Inner` (or, for a
+/// `Full` join, to a one-sided outer join) without changing the result. This lets
+/// cheaper inner-join physical operators and build-probe reordering kick in downstream.
+#[derive(Default, Debug)]
+pub struct EliminateOuterJoin {}
+
+impl EliminateOuterJoin {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for EliminateOuterJoin {
+    fn name(&self) -> &str {
+        "eliminate_outer_join"
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let LogicalPlan::Filter(Filter {
+            predicate,
+            input,
+            having,
+            ..
+        }) = &plan
+        else {
+            return Ok(Transformed::no(plan));
+        };
+        let LogicalPlan::Join(join) = input.as_ref() else {
+            return Ok(Transformed::no(plan));
+        };
+        if !matches!(join.join_type, JoinType::Outer(_) | JoinType::Full) {
+            return Ok(Transformed::no(plan));
+        }
+
+        let null_rejected = null_rejecting_columns(predicate);
+        let left_rejected = schema_has_any_of(join.left.schema(), &null_rejected);
+        let right_rejected = schema_has_any_of(join.right.schema(), &null_rejected);
+
+        let new_join_type = match join.join_type {
+            JoinType::Outer(JoinSide::Left) if right_rejected => JoinType::Inner,
+            JoinType::Outer(JoinSide::Right) if left_rejected => JoinType::Inner,
+            JoinType::Full => match (left_rejected, right_rejected) {
+                (true, true) => JoinType::Inner,
+                (true, false) => JoinType::Outer(JoinSide::Left),
+                (false, true) => JoinType::Outer(JoinSide::Right),
+                (false, false) => return Ok(Transformed::no(plan)),
+            },
+            _ => return Ok(Transformed::no(plan)),
+        };
+
+        let mut new_join = join.clone();
+        new_join.join_type = new_join_type;
+        new_join.schema = Arc::new(build_join_schema(
+            join.left.schema(),
+            join.right.schema(),
+            &new_join_type,
+        )?);
+        let new_filter = Filter::try_new_with_having(
+            predicate.clone(),
+            Arc::new(LogicalPlan::Join(new_join)),
+            *having,
+        )?;
+        Ok(Transformed::yes(LogicalPlan::Filter(new_filter)))
+    }
+}
+
+/// Returns the set of columns for which the predicate evaluates to `false` or `NULL`
+/// whenever that column is `NULL` -- i.e. the columns a `WHERE`/`Filter` using this
+/// predicate is guaranteed to reject rows on.
+///
+/// - A conjunction (`AND`) rejects on a column if *either* conjunct does, since the
+///   whole `AND` is false/null as soon as one side is.
+/// - A disjunction (`OR`) only rejects on a column if *all* branches reject on it,
+///   since a single true/non-null branch makes the whole `OR` survive.
+/// - Comparisons (`=`, `<`, `>`, ...) and most arithmetic propagate a `NULL` operand to
+///   a `NULL` result, so they reject on every column that forces either operand to
+///   `NULL` (see [`propagates_null_columns`]).
+/// - `IS NOT NULL` rejects on whatever would force its argument to `NULL`, since it
+///   itself evaluates to `false` (not `NULL`) in that case. `IS NULL` and
+///   null-coalescing functions are specifically designed to produce a non-null result
+///   from a `NULL` input, so they reject on nothing.
+fn null_rejecting_columns(expr: &Expr) -> HashSet<Column> {
+    match expr {
+        Expr::Column(c) => std::iter::once(c.clone()).collect(),
+        Expr::Not(arg) => propagates_null_columns(arg),
+        Expr::BinaryExpr(BinaryExpr { left, op: Operator::And, right }) => {
+            let mut cols = null_rejecting_columns(left);
+            cols.extend(null_rejecting_columns(right));
+            cols
+        }
+        Expr::BinaryExpr(BinaryExpr { left, op: Operator::Or, right }) => {
+            let left_cols = null_rejecting_columns(left);
+            let right_cols = null_rejecting_columns(right);
+            left_cols.intersection(&right_cols).cloned().collect()
+        }
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op:
+                Operator::Eq
+                | Operator::NotEq
+                | Operator::Lt
+                | Operator::LtEq
+                | Operator::Gt
+                | Operator::GtEq,
+            right,
+        }) => {
+            let mut cols = propagates_null_columns(left);
+            cols.extend(propagates_null_columns(right));
+            cols
+        }
+        // `x IS NOT NULL` is `false` (not `NULL`) when `x` is `NULL`, so it rejects the
+        // row on every column that forces `x` itself to `NULL`. `x IS NULL` is the one
+        // construct designed to produce a non-null (`true`) result from a `NULL` input,
+        // so it rejects on nothing.
+        Expr::IsNotNull(arg) => propagates_null_columns(arg),
+        Expr::IsNull(_) => HashSet::new(),
+        _ => HashSet::new(),
+    }
+}
+
+/// Returns the set of columns that, if `NULL`, force the *value* of `expr` itself to
+/// `NULL` (as opposed to [`null_rejecting_columns`], which reasons about a boolean
+/// predicate rejecting a row). Used for the operands of a comparison, since a `NULL`
+/// operand makes the comparison `NULL` regardless of what the other operand is.
+fn propagates_null_columns(expr: &Expr) -> HashSet<Column> {
+    match expr {
+        Expr::Column(c) => std::iter::once(c.clone()).collect(),
+        Expr::Not(arg) => propagates_null_columns(arg),
+        Expr::Cast(cast) => propagates_null_columns(&cast.expr),
+        Expr::TryCast(cast) => propagates_null_columns(&cast.expr),
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op:
+                Operator::Plus
+                | Operator::Minus
+                | Operator::Multiply
+                | Operator::Divide
+                | Operator::Modulo
+                | Operator::Eq
+                | Operator::NotEq
+                | Operator::Lt
+                | Operator::LtEq
+                | Operator::Gt
+                | Operator::GtEq,
+            right,
+        }) => {
+            let mut cols = propagates_null_columns(left);
+            cols.extend(propagates_null_columns(right));
+            cols
+        }
+        // `IS NULL`, `COALESCE`, `CASE`, and literals never propagate a `NULL` input to
+        // a `NULL` result by design -- conservatively assume anything else (including
+        // unrecognized scalar functions) may shield a `NULL` too.
+        _ => HashSet::new(),
+    }
+}
+
+fn schema_has_any_of(schema: &DFSchemaRef, columns: &HashSet<Column>) -> bool {
+    columns.iter().any(|c| schema.index_of_column(c).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_expr::{col, lit, logical_plan::table_scan, LogicalPlanBuilder};
+
+    fn assert_optimized_plan_equal(plan: LogicalPlan, expected: &str) -> Result<()> {
+        assert_optimized_plan_eq(Arc::new(EliminateOuterJoin {}), plan, expected)
+    }
+
+    fn left_right_plan() -> Result<LogicalPlanBuilder> {
+        table_scan(
+            Some("a"),
+            &Schema::new(vec![Field::new("k", DataType::UInt32, false)]),
+            None,
+        )?
+        .join(
+            table_scan(
+                Some("b"),
+                &Schema::new(vec![
+                    Field::new("k", DataType::UInt32, false),
+                    Field::new("x", DataType::Int32, true),
+                ]),
+                None,
+            )?
+            .build()?,
+            JoinType::Outer(JoinSide::Left),
+            (vec!["a.k"], vec!["b.k"]),
+            None,
+        )
+    }
+
+    #[test]
+    fn eq_predicate_demotes_left_outer_to_inner() -> Result<()> {
+        let plan = left_right_plan()?
+            .filter(col("b.x").eq(lit(100)))?
+            .build()?;
+
+        let expected = "\
+            Filter: b.x = Int32(100)\
+            \n  Inner Join: a.k = b.k\
+            \n    TableScan: a\
+            \n    TableScan: b\
+            ";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn is_not_null_predicate_demotes_left_outer_to_inner() -> Result<()> {
+        let plan = left_right_plan()?
+            .filter(col("b.x").is_not_null())?
+            .build()?;
+
+        let expected = "\
+            Filter: b.x IS NOT NULL\
+            \n  Inner Join: a.k = b.k\
+            \n    TableScan: a\
+            \n    TableScan: b\
+            ";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn is_null_predicate_is_not_null_rejecting() -> Result<()> {
+        let plan = left_right_plan()?
+            .filter(col("b.x").is_null())?
+            .build()?;
+
+        // `IS NULL` is designed to survive a NULL input, so the outer join must stay.
+        let expected = "\
+            Filter: b.x IS NULL\
+            \n  Left Join: a.k = b.k\
+            \n    TableScan: a\
+            \n    TableScan: b\
+            ";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn or_with_non_rejecting_branch_keeps_outer_join() -> Result<()> {
+        let plan = left_right_plan()?
+            .filter(col("b.x").eq(lit(100)).or(col("b.x").is_null()))?
+            .build()?;
+
+        // One OR branch (`IS NULL`) does not reject NULLs, so neither does the whole
+        // predicate, and the outer join must be preserved.
+        let expected = "\
+            Filter: b.x = Int32(100) OR b.x IS NULL\
+            \n  Left Join: a.k = b.k\
+            \n    TableScan: a\
+            \n    TableScan: b\
+            ";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn or_with_both_rejecting_branches_demotes_to_inner() -> Result<()> {
+        let plan = left_right_plan()?
+            .filter(col("b.x").eq(lit(100)).or(col("b.x").gt(lit(0))))?
+            .build()?;
+
+        let expected = "\
+            Filter: b.x = Int32(100) OR b.x > Int32(0)\
+            \n  Inner Join: a.k = b.k\
+            \n    TableScan: a\
+            \n    TableScan: b\
+            ";
+        assert_optimized_plan_equal(plan, expected)
+    }
+}
@@ -20,11 +20,12 @@
 use crate::optimizer::ApplyOrder;
 use crate::{OptimizerConfig, OptimizerRule};
 use datafusion_common::tree_node::Transformed;
-use datafusion_common::Result;
+use datafusion_common::{Column, DFSchemaRef, Result};
 use datafusion_expr::expr_rewriter::coerce_plan_expr_for_schema;
-use datafusion_expr::logical_plan::{JoinConstraint, JoinType, LogicalPlan};
-use datafusion_expr::{Aggregate, Expr, GroupingSet, Join};
+use datafusion_expr::logical_plan::{JoinConstraint, JoinSide, JoinType, LogicalPlan};
+use datafusion_expr::{Aggregate, Expr, Join};
 use itertools::Itertools;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 // Article: https://www.vldb.org/pvldb/vol4/p843-moerkotte.pdf
@@ -69,9 +70,9 @@ impl OptimizerRule for GroupByAndJoinToGroupJoin {
             right,
             on,
             filter,
-            join_type: JoinType::Left,
+            join_type,
             join_constraint,
-            schema: join_schema,
+            schema: _join_schema,
             null_equals_null,
             group_expr: _,
             aggr_expr: _,
@@ -80,28 +81,175 @@ impl OptimizerRule for GroupByAndJoinToGroupJoin {
             return Ok(Transformed::no(plan));
         };
 
-        if is_group_join(group_expr.clone(), on.clone()) {
-            let new_plan = LogicalPlan::Join(Join {
-                left,
-                right,
-                on,
-                filter,
-                join_type: JoinType::LeftGroup,
-                join_constraint,
-                schema: aggregate_schema,
-                null_equals_null,
-                group_expr: Some(group_expr),
-                aggr_expr: Some(aggr_expr),
-            });
-            Ok(Transformed::yes(new_plan))
-        } else {
-            Ok(Transformed::no(plan))
+        let Some((_group_side, group_join_type)) = resolve_group_join(
+            join_type,
+            &group_expr,
+            &aggr_expr,
+            &on,
+            &filter,
+            left.schema(),
+            right.schema(),
+        ) else {
+            return Ok(Transformed::no(plan));
+        };
+
+        let new_plan = LogicalPlan::Join(Join {
+            left,
+            right,
+            on,
+            filter,
+            join_type: group_join_type,
+            join_constraint,
+            schema: aggregate_schema,
+            null_equals_null,
+            group_expr: Some(group_expr),
+            aggr_expr: Some(aggr_expr),
+        });
+        Ok(Transformed::yes(new_plan))
+    }
+}
+
+/// The side of the join whose keys the `GROUP BY` groups by. The group-join
+/// accumulates rows from the other side into each group's aggregates. For the outer
+/// variants this side is also preserved (an unmatched row still produces a group); an
+/// `InnerGroup` join preserves neither side's unmatched rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GroupSide {
+    Left,
+    Right,
+}
+
+/// Returns the `(GroupSide, JoinType)` to rewrite `join_type` into, if
+/// `Aggregate{group_expr, aggr_expr}(Join{on, filter, join_type, ..})` satisfies the
+/// group-join applicability conditions from "Accelerating Queries with Group-By and
+/// Join by Groupjoin" (Moerkotte & Neumann) for one of the sides `join_type` allows to
+/// be grouped by:
+///
+/// - `Outer(Left)`/`Outer(Right)` only allow grouping by the preserved side, since that
+///   is the only side whose unmatched rows must still produce a (NULL-aggregate) group.
+/// - `Inner` allows grouping by either side, since neither side's unmatched rows need
+///   to be preserved.
+/// - `Full` allows grouping by either side too: both no-match directions are preserved
+///   by the `FullGroup` join regardless of which side is the grouping key.
+///
+/// For the chosen side, the rewrite is legal only when:
+/// 1. every grouping expression is a bare column reference (grouping sets and computed
+///    keys are rejected, since the group-join needs a concrete set of key columns),
+/// 2. the grouping columns are exactly that side's join keys (so grouping by it and
+///    probing it via the join key coincide), and the join is a non-empty equijoin,
+/// 3. every aggregate argument resolves against the *other* side's schema only -- the
+///    group-join accumulates the other side's rows per group, so a grouping-side
+///    reference would need per-row state the group-join does not keep around, and
+/// 4. any residual join filter references only the other side's columns, so it can be
+///    evaluated while probing without perturbing the group identity.
+fn resolve_group_join(
+    join_type: JoinType,
+    group_expr: &[Expr],
+    aggr_expr: &[Expr],
+    on: &[(Expr, Expr)],
+    filter: &Option<Expr>,
+    left_schema: &DFSchemaRef,
+    right_schema: &DFSchemaRef,
+) -> Option<(GroupSide, JoinType)> {
+    let candidates: &[(GroupSide, JoinType)] = match join_type {
+        JoinType::Outer(JoinSide::Left) => &[(GroupSide::Left, JoinType::LeftGroup)],
+        JoinType::Outer(JoinSide::Right) => &[(GroupSide::Right, JoinType::RightGroup)],
+        JoinType::Inner => &[
+            (GroupSide::Left, JoinType::InnerGroup),
+            (GroupSide::Right, JoinType::InnerGroup),
+        ],
+        JoinType::Full => &[
+            (GroupSide::Left, JoinType::FullGroup),
+            (GroupSide::Right, JoinType::FullGroup),
+        ],
+        _ => &[],
+    };
+
+    candidates
+        .iter()
+        .copied()
+        .find(|(side, _)| {
+            is_group_join(group_expr, aggr_expr, on, filter, *side, left_schema, right_schema)
+        })
+}
+
+fn is_group_join(
+    group_expr: &[Expr],
+    aggr_expr: &[Expr],
+    on: &[(Expr, Expr)],
+    filter: &Option<Expr>,
+    group_side: GroupSide,
+    left_schema: &DFSchemaRef,
+    right_schema: &DFSchemaRef,
+) -> bool {
+    if on.is_empty() {
+        return false;
+    }
+
+    // (1) Grouping keys must be bare columns -- grouping sets and computed expressions
+    // don't have a stable identity we can match against a single join key.
+    let Some(group_columns) = as_column_set(group_expr) else {
+        return false;
+    };
+
+    // (2) The grouping columns must equal the set of join keys on `group_side`.
+    let key_exprs = on.iter().map(|(l, r)| match group_side {
+        GroupSide::Left => l,
+        GroupSide::Right => r,
+    });
+    let Some(side_join_columns) = key_exprs
+        .map(|e| match e {
+            Expr::Column(c) => Some(c.clone()),
+            _ => None,
+        })
+        .collect::<Option<HashSet<Column>>>()
+    else {
+        return false;
+    };
+    if group_columns != side_join_columns {
+        return false;
+    }
+
+    // (3)/(4) Aggregates and any residual filter may only reference the other side --
+    // the group-join accumulates that side's rows per group, so a reference back into
+    // the grouped side has no per-row state to read from.
+    let group_side_schema = match group_side {
+        GroupSide::Left => left_schema,
+        GroupSide::Right => right_schema,
+    };
+    if aggr_expr
+        .iter()
+        .any(|e| references_schema(e, group_side_schema))
+    {
+        return false;
+    }
+    if let Some(filter) = filter {
+        if references_schema(filter, group_side_schema) {
+            return false;
         }
     }
+
+    true
+}
+
+/// Collects `exprs` into a set of their underlying columns, or `None` if any expression
+/// is not a bare column reference (e.g. a computed key or a `GroupingSet`).
+fn as_column_set(exprs: &[Expr]) -> Option<HashSet<Column>> {
+    exprs
+        .iter()
+        .map(|e| match e {
+            Expr::Column(c) => Some(c.clone()),
+            _ => None,
+        })
+        .collect()
 }
 
-fn is_group_join(group_expr: Vec<Expr>, on: Vec<(Expr, Expr)>) -> bool {
-    dbg!(group_expr[0] == on[0].0)
+/// Returns `true` if `expr` references at least one column of `schema`. An expression
+/// with no column references at all (e.g. a literal) trivially does not reference it.
+fn references_schema(expr: &Expr, schema: &DFSchemaRef) -> bool {
+    expr.column_refs()
+        .iter()
+        .any(|c| schema.index_of_column(c).is_ok())
 }
 
 #[cfg(test)]
@@ -112,7 +260,7 @@ mod tests {
     use crate::test::*;
     use arrow::datatypes::{DataType, Field, Schema};
     use datafusion_common::{config::ConfigOptions, Column};
-    use datafusion_expr::{col, logical_plan::table_scan, LogicalPlanBuilder};
+    use datafusion_expr::{col, lit, logical_plan::table_scan, LogicalPlanBuilder};
     use datafusion_functions_aggregate::expr_fn::{count, max, min};
 
     fn schema() -> Schema {
@@ -144,19 +292,266 @@ mod tests {
                 None,
             )?
             .build()?,
-            JoinType::Left,
+            JoinType::Outer(JoinSide::Left),
             (vec!["left.key"], vec!["right.key"]),
             None,
         )?
         .aggregate(vec![col("left.key")], vec![max(col("value"))])?
         .build()?;
 
+        let expected = "\
+            LeftGroup Join: left.key = right.key\
+            \n  TableScan: left\
+            \n  TableScan: right\
+            ";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    // A two-key left join grouped by exactly its left keys is rewritten to a
+    // `LeftGroup` join. That join preserves the left side: a left group with no
+    // matching right rows still appears, with `count` aggregating to 0 and every other
+    // aggregate to NULL, as a `Left` join followed by an `Aggregate` would produce.
+    // This test checks the rewritten plan only, not the aggregate values.
+    #[test]
+    fn multi_key_join_rewrites() -> Result<()> {
+        let left_schema = Schema::new(vec![
+            Field::new("k1", DataType::UInt32, false),
+            Field::new("k2", DataType::UInt32, false),
+        ]);
+        let right_schema = Schema::new(vec![
+            Field::new("k1", DataType::UInt32, false),
+            Field::new("k2", DataType::UInt32, false),
+            Field::new("value", DataType::UInt32, false),
+        ]);
+        let plan = table_scan(Some("left"), &left_schema, None)?
+            .join(
+                table_scan(Some("right"), &right_schema, None)?.build()?,
+                JoinType::Outer(JoinSide::Left),
+                (
+                    vec!["left.k1", "left.k2"],
+                    vec!["right.k1", "right.k2"],
+                ),
+                None,
+            )?
+            .aggregate(
+                vec![col("left.k1"), col("left.k2")],
+                vec![max(col("value"))],
+            )?
+            .build()?;
+
+        let expected = "\
+            LeftGroup Join: left.k1 = right.k1, left.k2 = right.k2\
+            \n  TableScan: left\
+            \n  TableScan: right\
+            ";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn right_aggregated_and_join_left() -> Result<()> {
+        let plan = table_scan(
+            Some("left"),
+            &Schema::new(vec![
+                Field::new("key", DataType::UInt32, false),
+                Field::new("value", DataType::UInt32, false),
+            ]),
+            None,
+        )?
+        .join(
+            table_scan(
+                Some("right"),
+                &Schema::new(vec![Field::new("key", DataType::UInt32, false)]),
+                None,
+            )?
+            .build()?,
+            JoinType::Outer(JoinSide::Right),
+            (vec!["left.key"], vec!["right.key"]),
+            None,
+        )?
+        .aggregate(vec![col("right.key")], vec![max(col("value"))])?
+        .build()?;
+
+        let expected = "\
+            RightGroup Join: left.key = right.key\
+            \n  TableScan: left\
+            \n  TableScan: right\
+            ";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn inner_group_by_right_key_aggregates_left() -> Result<()> {
+        let plan = table_scan(
+            Some("left"),
+            &Schema::new(vec![
+                Field::new("key", DataType::UInt32, false),
+                Field::new("value", DataType::UInt32, false),
+            ]),
+            None,
+        )?
+        .join(
+            table_scan(
+                Some("right"),
+                &Schema::new(vec![Field::new("key", DataType::UInt32, false)]),
+                None,
+            )?
+            .build()?,
+            JoinType::Inner,
+            (vec!["left.key"], vec!["right.key"]),
+            None,
+        )?
+        .aggregate(vec![col("right.key")], vec![max(col("value"))])?
+        .build()?;
+
+        let expected = "\
+            InnerGroup Join: left.key = right.key\
+            \n  TableScan: left\
+            \n  TableScan: right\
+            ";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn full_group_by_left_key_aggregates_right() -> Result<()> {
+        let plan = table_scan(
+            Some("left"),
+            &Schema::new(vec![Field::new("key", DataType::UInt32, false)]),
+            None,
+        )?
+        .join(
+            table_scan(
+                Some("right"),
+                &Schema::new(vec![
+                    Field::new("key", DataType::UInt32, false),
+                    Field::new("value", DataType::UInt32, false),
+                ]),
+                None,
+            )?
+            .build()?,
+            JoinType::Full,
+            (vec!["left.key"], vec!["right.key"]),
+            None,
+        )?
+        .aggregate(vec![col("left.key")], vec![max(col("value"))])?
+        .build()?;
+
+        let expected = "\
+            FullGroup Join: left.key = right.key\
+            \n  TableScan: left\
+            \n  TableScan: right\
+            ";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn rejects_semi_join() -> Result<()> {
+        let plan = table_scan(
+            Some("left"),
+            &Schema::new(vec![Field::new("key", DataType::UInt32, false)]),
+            None,
+        )?
+        .join(
+            table_scan(
+                Some("right"),
+                &Schema::new(vec![
+                    Field::new("key", DataType::UInt32, false),
+                    Field::new("value", DataType::UInt32, false),
+                ]),
+                None,
+            )?
+            .build()?,
+            JoinType::Semi(JoinSide::Left),
+            (vec!["left.key"], vec!["right.key"]),
+            None,
+        )?
+        .aggregate(vec![col("left.key")], vec![max(col("value"))])?
+        .build()?;
+
+        // A semi join never offers a grouping side it is safe to group-join on, so the
+        // rule must leave the plan untouched.
         let expected = "\
             Aggregate: groupBy=[[left.key]], aggr=[[max(right.value)]]\
-            \n  Left Join: left.key = right.key\
+            \n  LeftSemi Join: left.key = right.key\
             \n    TableScan: left\
             \n    TableScan: right\
             ";
         assert_optimized_plan_equal(plan, expected)
     }
+
+    #[test]
+    fn rejects_non_equijoin() {
+        let left_schema = Arc::new(
+            datafusion_common::DFSchema::try_from_qualified_schema("left", &schema()).unwrap(),
+        );
+        let right_schema = left_schema.clone();
+        assert!(!is_group_join(
+            &[col("left.key")],
+            &[max(col("value"))],
+            &[],
+            &None,
+            GroupSide::Left,
+            &left_schema,
+            &right_schema,
+        ));
+    }
+
+    #[test]
+    fn rejects_computed_grouping_key() {
+        let left_schema = Arc::new(
+            datafusion_common::DFSchema::try_from_qualified_schema("left", &schema()).unwrap(),
+        );
+        let right_schema = Arc::new(
+            datafusion_common::DFSchema::try_from_qualified_schema("right", &schema()).unwrap(),
+        );
+        let on = vec![(col("left.key"), col("right.key"))];
+        assert!(!is_group_join(
+            &[col("left.key") + lit(1)],
+            &[max(col("value"))],
+            &on,
+            &None,
+            GroupSide::Left,
+            &left_schema,
+            &right_schema,
+        ));
+    }
+
+    #[test]
+    fn rejects_aggregate_over_grouped_side_column() {
+        let left_schema = Arc::new(
+            datafusion_common::DFSchema::try_from_qualified_schema("left", &schema()).unwrap(),
+        );
+        let right_schema = Arc::new(
+            datafusion_common::DFSchema::try_from_qualified_schema("right", &schema()).unwrap(),
+        );
+        let on = vec![(col("left.key"), col("right.key"))];
+        assert!(!is_group_join(
+            &[col("left.key")],
+            &[max(col("left.value"))],
+            &on,
+            &None,
+            GroupSide::Left,
+            &left_schema,
+            &right_schema,
+        ));
+    }
+
+    #[test]
+    fn rejects_filter_over_grouped_side_column() {
+        let left_schema = Arc::new(
+            datafusion_common::DFSchema::try_from_qualified_schema("left", &schema()).unwrap(),
+        );
+        let right_schema = Arc::new(
+            datafusion_common::DFSchema::try_from_qualified_schema("right", &schema()).unwrap(),
+        );
+        let on = vec![(col("left.key"), col("right.key"))];
+        assert!(!is_group_join(
+            &[col("left.key")],
+            &[max(col("right.value"))],
+            &on,
+            &Some(col("left.value").gt(lit(0))),
+            GroupSide::Left,
+            &left_schema,
+            &right_schema,
+        ));
+    }
 }
@@ -0,0 +1,331 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`PropagateEmptyRelation`] propagates known-empty inputs through joins
+
+use crate::{OptimizerConfig, OptimizerRule};
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::Result;
+use datafusion_expr::logical_plan::{EmptyRelation, Join, JoinSide, JoinType, LogicalPlan};
+use std::sync::Arc;
+
+/// Replaces a [`Join`] with a simpler plan when one (or both) of its inputs is a
+/// provably empty relation.
+///
+/// Every [`JoinType`] must declare its own empty-input behavior here rather than
+/// falling back to a generic default, since getting this wrong silently drops rows a
+/// real execution would have produced (most notably for the group-join variants,
+/// where an empty *non-grouped* side does not make the join empty -- it just means
+/// every group aggregates over zero rows).
+#[derive(Default, Debug)]
+pub struct PropagateEmptyRelation {}
+
+impl PropagateEmptyRelation {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for PropagateEmptyRelation {
+    fn name(&self) -> &str {
+        "propagate_empty_relation"
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let LogicalPlan::Join(join) = &plan else {
+            return Ok(Transformed::no(plan));
+        };
+
+        let left_empty = is_empty_relation(&join.left);
+        let right_empty = is_empty_relation(&join.right);
+        if !left_empty && !right_empty {
+            return Ok(Transformed::no(plan));
+        }
+
+        match empty_propagation(join.join_type, left_empty, right_empty) {
+            EmptyPropagation::Empty => Ok(Transformed::yes(LogicalPlan::EmptyRelation(
+                EmptyRelation {
+                    produce_one_row: false,
+                    schema: join.schema.clone(),
+                },
+            ))),
+            EmptyPropagation::PassThroughLeft => {
+                Ok(Transformed::yes(join.left.as_ref().clone()))
+            }
+            EmptyPropagation::PassThroughRight => {
+                Ok(Transformed::yes(join.right.as_ref().clone()))
+            }
+            EmptyPropagation::NoChange => Ok(Transformed::no(plan)),
+        }
+    }
+}
+
+/// What an empty input lets a [`Join`] collapse to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EmptyPropagation {
+    /// The join can never produce a row; replace it with an `EmptyRelation` carrying
+    /// the join's own output schema.
+    Empty,
+    /// The join degenerates to exactly its left input (only valid when the join's
+    /// output schema is already the left input's schema, as for `LeftAnti`).
+    PassThroughLeft,
+    /// The join degenerates to exactly its right input (as for `RightAnti`).
+    PassThroughRight,
+    /// Neither input's emptiness lets this join collapse; leave the plan untouched.
+    NoChange,
+}
+
+/// Declares, for every [`JoinType`], what an empty left and/or empty right input lets
+/// it collapse to.
+fn empty_propagation(
+    join_type: JoinType,
+    left_empty: bool,
+    right_empty: bool,
+) -> EmptyPropagation {
+    use EmptyPropagation::{Empty, NoChange, PassThroughLeft, PassThroughRight};
+
+    match join_type {
+        // An inner join (plain or grouped) only ever produces matched pairs, so either
+        // side being empty leaves no pairs to produce.
+        JoinType::Inner | JoinType::InnerGroup => {
+            if left_empty || right_empty {
+                Empty
+            } else {
+                NoChange
+            }
+        }
+        JoinType::Semi(_) => {
+            if left_empty || right_empty {
+                Empty
+            } else {
+                NoChange
+            }
+        }
+        // These all preserve the left side: every left row (matched or not) always
+        // contributes to the output, and grouping/marking is keyed on left rows, so an
+        // empty left input leaves nothing to preserve. An empty *right* input does not
+        // make these empty -- the preserved left rows still appear, just unmatched (for
+        // `LeftGroup`, every left group still appears with a zero-count/NULL
+        // aggregate), so that case is `NoChange`.
+        JoinType::Outer(JoinSide::Left) | JoinType::LeftGroup | JoinType::LeftMark => {
+            if left_empty {
+                Empty
+            } else {
+                NoChange
+            }
+        }
+        // Mirror of the above, preserving the right side instead.
+        JoinType::Outer(JoinSide::Right) | JoinType::RightGroup => {
+            if right_empty {
+                Empty
+            } else {
+                NoChange
+            }
+        }
+        // `LeftAnti` returns left rows with no match in the right input. If the right
+        // input is empty, *every* left row qualifies unconditionally, so the join
+        // degenerates to its left input outright. If the left input is empty, there
+        // are no left rows to test, so the join is empty.
+        JoinType::Anti(JoinSide::Left) => {
+            if left_empty {
+                Empty
+            } else if right_empty {
+                PassThroughLeft
+            } else {
+                NoChange
+            }
+        }
+        // Mirror of `LeftAnti`.
+        JoinType::Anti(JoinSide::Right) => {
+            if right_empty {
+                Empty
+            } else if left_empty {
+                PassThroughRight
+            } else {
+                NoChange
+            }
+        }
+        // `Full`/`FullGroup` preserve both sides independently, so neither side being
+        // empty *alone* collapses them (the other side's rows still need to be
+        // NULL-extended and emitted). `FullGroup` additionally can't say which side is
+        // the grouping side from `JoinType` alone, so that case is left untouched too.
+        // But with *both* sides empty there are no rows on either side to preserve, so
+        // the join is empty regardless of which side is grouped.
+        JoinType::Full | JoinType::FullGroup => {
+            if left_empty && right_empty {
+                Empty
+            } else {
+                NoChange
+            }
+        }
+    }
+}
+
+fn is_empty_relation(plan: &LogicalPlan) -> bool {
+    matches!(
+        plan,
+        LogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row: false,
+            ..
+        })
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::DFSchema;
+    use datafusion_expr::{col, logical_plan::table_scan, LogicalPlanBuilder};
+    use datafusion_functions_aggregate::expr_fn::max;
+
+    fn assert_optimized_plan_equal(plan: LogicalPlan, expected: &str) -> Result<()> {
+        assert_optimized_plan_eq(Arc::new(PropagateEmptyRelation {}), plan, expected)
+    }
+
+    /// A provably empty relation with the same (qualified) schema `table_scan(qualifier,
+    /// schema, None)` would have produced, so it can stand in for that scan in a join.
+    fn empty_relation(qualifier: &str, schema: &Schema) -> Result<LogicalPlan> {
+        Ok(LogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row: false,
+            schema: Arc::new(DFSchema::try_from_qualified_schema(qualifier, schema)?),
+        }))
+    }
+
+    fn left_schema() -> Schema {
+        Schema::new(vec![Field::new("key", DataType::UInt32, false)])
+    }
+
+    fn right_schema() -> Schema {
+        Schema::new(vec![
+            Field::new("key", DataType::UInt32, false),
+            Field::new("value", DataType::UInt32, false),
+        ])
+    }
+
+    #[test]
+    fn inner_join_with_empty_left_is_empty() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(empty_relation("left", &left_schema())?)
+            .join(
+                table_scan(Some("right"), &right_schema(), None)?.build()?,
+                JoinType::Inner,
+                (vec!["left.key"], vec!["right.key"]),
+                None,
+            )?
+            .build()?;
+
+        assert_optimized_plan_equal(plan, "EmptyRelation")
+    }
+
+    #[test]
+    fn left_outer_join_with_empty_left_is_empty() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(empty_relation("left", &left_schema())?)
+            .join(
+                table_scan(Some("right"), &right_schema(), None)?.build()?,
+                JoinType::Outer(JoinSide::Left),
+                (vec!["left.key"], vec!["right.key"]),
+                None,
+            )?
+            .build()?;
+
+        assert_optimized_plan_equal(plan, "EmptyRelation")
+    }
+
+    #[test]
+    fn left_anti_join_with_empty_right_passes_through_left() -> Result<()> {
+        let plan = table_scan(Some("left"), &left_schema(), None)?
+            .join(
+                empty_relation("right", &right_schema())?,
+                JoinType::Anti(JoinSide::Left),
+                (vec!["left.key"], vec!["right.key"]),
+                None,
+            )?
+            .build()?;
+
+        assert_optimized_plan_equal(plan, "TableScan: left")
+    }
+
+    #[test]
+    fn left_group_join_with_empty_right_is_unchanged() -> Result<()> {
+        let mut plan = table_scan(Some("left"), &left_schema(), None)?
+            .join(
+                empty_relation("right", &right_schema())?,
+                JoinType::Outer(JoinSide::Left),
+                (vec!["left.key"], vec!["right.key"]),
+                None,
+            )?
+            .build()?;
+        let LogicalPlan::Join(join) = &mut plan else {
+            unreachable!()
+        };
+        join.join_type = JoinType::LeftGroup;
+        join.group_expr = Some(vec![col("left.key")]);
+        join.aggr_expr = Some(vec![max(col("value"))]);
+
+        // An empty right input must not make the group-join empty -- every left group
+        // still has to appear with a zero-count/NULL aggregate.
+        let expected = "\
+            LeftGroup Join: left.key = right.key\
+            \n  TableScan: left\
+            \n  EmptyRelation\
+            ";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn full_join_with_both_inputs_empty_is_empty() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(empty_relation("left", &left_schema())?)
+            .join(
+                empty_relation("right", &right_schema())?,
+                JoinType::Full,
+                (vec!["left.key"], vec!["right.key"]),
+                None,
+            )?
+            .build()?;
+
+        assert_optimized_plan_equal(plan, "EmptyRelation")
+    }
+
+    #[test]
+    fn full_join_with_only_left_empty_is_unchanged() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(empty_relation("left", &left_schema())?)
+            .join(
+                table_scan(Some("right"), &right_schema(), None)?.build()?,
+                JoinType::Full,
+                (vec!["left.key"], vec!["right.key"]),
+                None,
+            )?
+            .build()?;
+
+        let expected = "\
+            Full Join: left.key = right.key\
+            \n  EmptyRelation\
+            \n  TableScan: right\
+            ";
+        assert_optimized_plan_equal(plan, expected)
+    }
+}